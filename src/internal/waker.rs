@@ -1,19 +1,89 @@
 use std::collections::VecDeque;
+use std::ptr;
 use std::sync::Arc;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
+use std::task;
 
 use parking_lot::Mutex;
 
 use internal::context::{self, Context};
 use internal::select::CaseId;
 
-/// A selection case, identified by a `Context` and a `CaseId`.
+/// The terminal state of an `AsyncSlot`: either still waiting, or claimed for exactly one of
+/// these reasons. Mirrors the distinction `Context::try_abort`/the new disconnect reason code
+/// make for thread cases, so a polled future can tell "I was selected", "I was aborted (lost a
+/// race / timed out)" and "the channel disconnected" apart.
+const WAITING: usize = 0;
+const SELECTED: usize = 1;
+const ABORTED: usize = 2;
+const DISCONNECTED: usize = 3;
+
+/// The parked destination a registered case wakes up when it's selected or aborted.
+///
+/// Most cases belong to a blocking thread, parked on a `Context`. Async cases instead carry a
+/// `std::task::Waker`, borrowed from an executor task polling a channel future.
+pub enum Waiter {
+    /// A thread blocked in `send`/`recv`/`select`.
+    Thread(Arc<Context>),
+
+    /// A task polling a channel future, backed by the slot that arbitrates the CAS race to
+    /// claim it.
+    Async(Arc<AsyncSlot>),
+}
+
+/// Shared state backing an async (`Future`-based) registration.
+///
+/// This plays the same role for an async case that `Context` plays for a thread: it's the thing
+/// `try_select`/`try_abort` race to claim, and it's cheap to check from the polling side without
+/// going back through the `Waker`.
+pub struct AsyncSlot {
+    /// `WAITING` until whichever side (a waking channel end, or the task giving up) wins the
+    /// race and claims the slot for one of `SELECTED`/`ABORTED`/`DISCONNECTED`.
+    state: AtomicUsize,
+
+    /// The waker to invoke once this slot is claimed by `try_select`/`try_abort`/`try_disconnect`.
+    waker: task::Waker,
+}
+
+impl AsyncSlot {
+    fn new(waker: task::Waker) -> Self {
+        AsyncSlot {
+            state: AtomicUsize::new(WAITING),
+            waker,
+        }
+    }
+
+    fn try_select(&self) -> bool {
+        self.state
+            .compare_exchange(WAITING, SELECTED, Ordering::SeqCst, Ordering::SeqCst)
+            .is_ok()
+    }
+
+    fn try_abort(&self) -> bool {
+        self.state
+            .compare_exchange(WAITING, ABORTED, Ordering::SeqCst, Ordering::SeqCst)
+            .is_ok()
+    }
+
+    fn try_disconnect(&self) -> bool {
+        self.state
+            .compare_exchange(WAITING, DISCONNECTED, Ordering::SeqCst, Ordering::SeqCst)
+            .is_ok()
+    }
+
+    /// Returns `true` if this slot was claimed by `disconnect` rather than an ordinary abort.
+    pub fn is_disconnected(&self) -> bool {
+        self.state.load(Ordering::SeqCst) == DISCONNECTED
+    }
+}
+
+/// A selection case, identified by a `Waiter` and a `CaseId`.
 ///
 /// Note that multiple threads could be operating on a single channel end, as well as a single
 /// thread on multiple different channel ends.
 pub struct Case {
-    /// A context associated with the thread owning this case.
-    pub context: Arc<Context>,
+    /// The thread or task owning this case.
+    pub waiter: Waiter,
 
     /// The case ID.
     pub case_id: CaseId,
@@ -25,12 +95,44 @@ pub struct Case {
 ///
 /// This data structure is used for registering selection cases before blocking and waking them
 /// up when the channel receives a message, sends one, or gets closed.
+///
+/// Two kinds of waiters are tracked separately:
+///
+/// - `selectors`: cases that are racing to *win* a `try_select` and claim a single message. These
+///   are woken one at a time by `wake_one`.
+/// - `observers`: cases that only want to be told "something changed, go re-check the channel"
+///   without claiming anything. A blocked `send` on an array channel watches the waker this way
+///   so it can re-check capacity once a reader drains a slot, and `select!` uses it to probe
+///   readiness without committing to a case.
+///
+/// `selectors` additionally has a lock-free fast path (see `fast`) for the common SPSC /
+/// low-contention case of at most one registered selector, so that case never takes the mutex.
 pub struct Waker {
-    /// The list of registered selection cases.
-    cases: Mutex<VecDeque<Case>>,
+    /// Lock-free fast path for the common single-waiter case: holds a lone selector case without
+    /// ever touching `selectors`'s mutex. `push_selector` only fills it when no other selector is
+    /// registered; as soon as a second selector registers, whatever sits here is demoted into the
+    /// deque, and `push_selector` doesn't fill it again until the deque drains completely. A case
+    /// put back by `restore_fast` can still land here while the deque is non-empty, so `wake_one`
+    /// goes on to scan `selectors` when this slot yields nothing.
+    fast: AtomicPtr<Case>,
+
+    /// The list of registered selection cases, used once there's more than one waiter.
+    selectors: Mutex<VecDeque<Case>>,
+
+    /// Number of cases in `selectors` plus `fast`.
+    selectors_len: AtomicUsize,
 
-    /// Number of cases in the list.
-    len: AtomicUsize,
+    /// Index `wake_one` resumes scanning `selectors` from, so repeated calls don't keep favoring
+    /// whichever case happens to sit near the front of the deque. Advanced past every case that
+    /// is skipped or loses its `try_select`, which gives registered cases (approximate) FIFO
+    /// fairness instead of letting one starve while its neighbors keep winning.
+    next: AtomicUsize,
+
+    /// The list of registered observer cases.
+    observers: Mutex<VecDeque<Case>>,
+
+    /// Number of cases in `observers`.
+    observers_len: AtomicUsize,
 }
 
 // TODO: inline everything?
@@ -39,41 +141,169 @@ impl Waker {
     #[inline]
     pub fn new() -> Self {
         Waker {
-            cases: Mutex::new(VecDeque::new()),
-            len: AtomicUsize::new(0),
+            fast: AtomicPtr::new(ptr::null_mut()),
+            selectors: Mutex::new(VecDeque::new()),
+            selectors_len: AtomicUsize::new(0),
+            next: AtomicUsize::new(0),
+            observers: Mutex::new(VecDeque::new()),
+            observers_len: AtomicUsize::new(0),
         }
     }
 
     /// Registers the current thread with `case_id`.
     pub fn register(&self, case_id: CaseId) {
-        let mut cases = self.cases.lock();
-        cases.push_back(Case {
-            context: context::current(),
+        self.push_selector(Case {
+            waiter: Waiter::Thread(context::current()),
             case_id,
             packet: 0,
         });
-        self.len.store(cases.len(), Ordering::SeqCst);
     }
 
     pub fn register_with_packet(&self, case_id: CaseId, packet: usize) {
-        let mut cases = self.cases.lock();
-        cases.push_back(Case {
-            context: context::current(),
+        self.push_selector(Case {
+            waiter: Waiter::Thread(context::current()),
+            case_id,
+            packet,
+        });
+    }
+
+    /// Registers a `std::task::Waker` with `case_id`, for async `send`/`recv` adapters.
+    ///
+    /// The same `try_select`-before-wake invariant as for threads applies here: whichever side
+    /// wins the CAS on the returned slot's claim flag is the only one allowed to act on the
+    /// message, so a packet is never handed to a future that already dropped/cancelled its case.
+    /// If the future is dropped before being woken, it must call `unregister` with `case_id`.
+    pub fn register_async(&self, case_id: CaseId, packet: usize, waker: task::Waker) -> Arc<AsyncSlot> {
+        let slot = Arc::new(AsyncSlot::new(waker));
+        self.push_selector(Case {
+            waiter: Waiter::Async(slot.clone()),
             case_id,
             packet,
         });
-        self.len.store(cases.len(), Ordering::SeqCst);
+        slot
+    }
+
+    /// Pushes `case` onto the selectors list, preferring the lock-free `fast` slot whenever it
+    /// looks like there isn't already another waiter.
+    ///
+    /// `selectors_len` is bumped with `fetch_add` *before* `case` is made reachable through
+    /// `fast` or `selectors`, and every removal below undoes its own bump with a matching
+    /// `fetch_sub`. Using a running total like this (rather than overwriting it with a freshly
+    /// computed `cases.len()`) is what keeps the counter correct no matter how a fast-path
+    /// register/remove interleaves with a concurrent mutex-guarded one — a `store` computed from
+    /// one side can otherwise land after, and clobber, an update made by the other.
+    fn push_selector(&self, mut case: Case) {
+        let prev = self.selectors_len.fetch_add(1, Ordering::SeqCst);
+
+        if prev == 0 && self.fast.load(Ordering::SeqCst).is_null() {
+            let boxed = Box::into_raw(Box::new(case));
+
+            if self
+                .fast
+                .compare_exchange(ptr::null_mut(), boxed, Ordering::SeqCst, Ordering::SeqCst)
+                .is_ok()
+            {
+                return;
+            }
+
+            // Lost the race for the fast slot; reclaim our case and fall back to the deque.
+            case = *unsafe { Box::from_raw(boxed) };
+        }
+
+        let mut cases = self.selectors.lock();
+        if let Some(demoted) = self.take_fast() {
+            cases.push_back(demoted);
+        }
+        cases.push_back(case);
+    }
+
+    /// Takes ownership of whatever case currently sits in the lock-free fast slot, if any.
+    ///
+    /// This is the only sound way to look at the pointee of `fast`: the `swap` atomically claims
+    /// it, so by the time we dereference the returned pointer via `Box::from_raw` no other thread
+    /// can be concurrently doing the same (and freeing it out from under us).
+    fn take_fast(&self) -> Option<Case> {
+        let case = self.fast.swap(ptr::null_mut(), Ordering::SeqCst);
+        if case.is_null() {
+            None
+        } else {
+            Some(*unsafe { Box::from_raw(case) })
+        }
+    }
+
+    /// Puts a case back into the fast slot after `take_fast` claimed it but it turned out not to
+    /// be the one a caller wanted, without ever exposing a not-yet-claimed pointer to inspection.
+    ///
+    /// If another case has since taken the fast slot, `case` is demoted into `selectors` instead —
+    /// exactly as if it had lost the race for `fast` in `push_selector`.
+    fn restore_fast(&self, case: Case) {
+        let boxed = Box::into_raw(Box::new(case));
+
+        if self
+            .fast
+            .compare_exchange(ptr::null_mut(), boxed, Ordering::SeqCst, Ordering::SeqCst)
+            .is_ok()
+        {
+            return;
+        }
+
+        let case = *unsafe { Box::from_raw(boxed) };
+        self.selectors.lock().push_back(case);
     }
 
     /// Unregisters the current thread with `case_id`.
     pub fn unregister(&self, case_id: CaseId) -> Option<Case> {
-        if self.len.load(Ordering::SeqCst) > 0 {
-            let mut cases = self.cases.lock();
+        if self.selectors_len.load(Ordering::SeqCst) == 0 {
+            return None;
+        }
+
+        // Claim whatever sits in the fast slot before inspecting it (see `take_fast`); only once
+        // we exclusively own it is it safe to check its `case_id`.
+        if let Some(case) = self.take_fast() {
+            if case.case_id == case_id {
+                self.selectors_len.fetch_sub(1, Ordering::SeqCst);
+                return Some(case);
+            }
+
+            self.restore_fast(case);
+        }
+
+        let mut cases = self.selectors.lock();
+
+        if let Some((i, _)) = cases.iter().enumerate().find(|&(_, case)| case.case_id == case_id) {
+            let case = cases.remove(i);
+            self.selectors_len.fetch_sub(1, Ordering::SeqCst);
+            Self::maybe_shrink(&mut cases);
+            case
+        } else {
+            None
+        }
+    }
+
+    /// Registers the current thread with `case_id` as an observer.
+    ///
+    /// Observers aren't racing to claim a message: they just want to be poked when the channel's
+    /// state changes so they can go re-scan it. Call `unwatch` once the thread is done waiting
+    /// (whether it was notified or gave up some other way).
+    pub fn watch(&self, case_id: CaseId) {
+        let mut observers = self.observers.lock();
+        observers.push_back(Case {
+            waiter: Waiter::Thread(context::current()),
+            case_id,
+            packet: 0,
+        });
+        self.observers_len.store(observers.len(), Ordering::SeqCst);
+    }
 
-            if let Some((i, _)) = cases.iter().enumerate().find(|&(_, case)| case.case_id == case_id) {
-                let case = cases.remove(i);
-                self.len.store(cases.len(), Ordering::SeqCst);
-                Self::maybe_shrink(&mut cases);
+    /// Unregisters the current thread's observer case with `case_id`.
+    pub fn unwatch(&self, case_id: CaseId) -> Option<Case> {
+        if self.observers_len.load(Ordering::SeqCst) > 0 {
+            let mut observers = self.observers.lock();
+
+            if let Some((i, _)) = observers.iter().enumerate().find(|&(_, case)| case.case_id == case_id) {
+                let case = observers.remove(i);
+                self.observers_len.store(observers.len(), Ordering::SeqCst);
+                Self::maybe_shrink(&mut observers);
                 case
             } else {
                 None
@@ -85,21 +315,79 @@ impl Waker {
 
     #[inline]
     pub fn wake_one(&self) -> Option<Case> {
-        if self.len.load(Ordering::SeqCst) > 0 {
-            let thread_id = context::current_thread_id();
-            let mut cases = self.cases.lock();
-
-            for i in 0..cases.len() {
-                if cases[i].context.thread.id() != thread_id {
-                    if cases[i].context.try_select(cases[i].case_id, cases[i].packet) {
-                        let case = cases.remove(i).unwrap();
-                        self.len.store(cases.len(), Ordering::SeqCst);
-                        Self::maybe_shrink(&mut cases);
-
-                        drop(cases);
-                        case.context.unpark();
-                        return Some(case);
-                    }
+        if self.selectors_len.load(Ordering::SeqCst) == 0 {
+            return None;
+        }
+
+        let thread_id = context::current_thread_id();
+
+        // Lock-free fast path: with a single waiter parked in `fast`, claim it with one swap and
+        // never touch the mutex at all.
+        //
+        // `take_fast` claims the slot before we look at the case it held, so we're never
+        // inspecting a `Case` that a concurrent `take_fast`/`unregister`/`wake_one` elsewhere
+        // could simultaneously be freeing.
+        if let Some(case) = self.take_fast() {
+            let is_current_thread = match &case.waiter {
+                Waiter::Thread(context) => context.thread.id() == thread_id,
+                Waiter::Async(_) => false,
+            };
+
+            if is_current_thread {
+                // Not a case we're allowed to wake; put it back and fall through to the slow
+                // path in case another waiter is sitting in `selectors`.
+                self.restore_fast(case);
+            } else {
+                // Decrement only after `take_fast` has claimed the case, mirroring
+                // `push_selector`'s ordering: a reader that races this and sees the stale
+                // (higher) count just takes a needless look at `fast`/`selectors` and finds
+                // nothing there, rather than skipping a case that's actually still waiting.
+                self.selectors_len.fetch_sub(1, Ordering::SeqCst);
+
+                if Self::try_select(&case) {
+                    Self::wake(&case);
+                    return Some(case);
+                }
+
+                // Lost the race after all (e.g. concurrently aborted); fall through and try the
+                // waiters in `selectors`, which need not be empty (see `fast`).
+            }
+        }
+
+        // Slow path: scan the waiters registered in `selectors`.
+        if self.selectors_len.load(Ordering::SeqCst) > 0 {
+            let mut cases = self.selectors.lock();
+
+            // Resume scanning where the last successful `wake_one` left off instead of always
+            // starting at the front, so a case that keeps losing isn't perpetually shadowed by
+            // whichever case happens to sit ahead of it.
+            let len = cases.len();
+            if len == 0 {
+                return None;
+            }
+            let start = self.next.load(Ordering::SeqCst) % len;
+
+            for offset in 0..len {
+                let i = (start + offset) % len;
+
+                let is_current_thread = match &cases[i].waiter {
+                    Waiter::Thread(context) => context.thread.id() == thread_id,
+                    Waiter::Async(_) => false,
+                };
+
+                if !is_current_thread && Self::try_select(&cases[i]) {
+                    let case = cases.remove(i).unwrap();
+                    self.selectors_len.fetch_sub(1, Ordering::SeqCst);
+
+                    // Resume the next scan right after the case we just removed, so its former
+                    // neighbors get first crack at the following wake-up instead of the front of
+                    // the deque.
+                    self.next.store(i, Ordering::SeqCst);
+                    Self::maybe_shrink(&mut cases);
+
+                    drop(cases);
+                    Self::wake(&case);
+                    return Some(case);
                 }
             }
         }
@@ -107,38 +395,195 @@ impl Waker {
         None
     }
 
-    /// Aborts all currently registered selection cases.
+    /// Wakes up every registered observer so it goes and re-scans the channel.
+    ///
+    /// Unlike `wake_one`, this never hands a message to anyone: it just pokes each observer via
+    /// `try_select`/`try_abort` so it retries, then drops them from the list. The selectors list
+    /// is left untouched.
+    pub fn notify(&self) {
+        if self.observers_len.load(Ordering::SeqCst) > 0 {
+            let mut observers = self.observers.lock();
+
+            self.observers_len.store(0, Ordering::SeqCst);
+            for case in observers.drain(..) {
+                if Self::try_select(&case) || Self::try_abort(&case) {
+                    Self::wake(&case);
+                }
+            }
+
+            Self::maybe_shrink(&mut observers);
+        }
+    }
+
+    /// Aborts all currently registered selection cases, both selectors and observers.
     pub fn abort_all(&self) {
-        if self.len.load(Ordering::SeqCst) > 0 {
-            let mut cases = self.cases.lock();
+        if self.selectors_len.load(Ordering::SeqCst) > 0 {
+            // Subtract exactly the number of cases we actually drain below, rather than
+            // clobbering the counter with an absolute 0: a `push_selector` racing with us (and
+            // landing in `fast` right after our `take_fast` below) is still accounted for
+            // correctly, instead of having its `fetch_add` wiped out by our reset.
+            let mut removed = 0;
+
+            if let Some(case) = self.take_fast() {
+                removed += 1;
+                if Self::try_abort(&case) {
+                    Self::wake(&case);
+                }
+            }
+
+            let mut cases = self.selectors.lock();
+            removed += cases.len();
+
+            for case in cases.drain(..) {
+                if Self::try_abort(&case) {
+                    Self::wake(&case);
+                }
+            }
+
+            self.selectors_len.fetch_sub(removed, Ordering::SeqCst);
+            Self::maybe_shrink(&mut cases);
+        }
+
+        if self.observers_len.load(Ordering::SeqCst) > 0 {
+            let mut observers = self.observers.lock();
+
+            self.observers_len.store(0, Ordering::SeqCst);
+            for case in observers.drain(..) {
+                if Self::try_abort(&case) {
+                    Self::wake(&case);
+                }
+            }
+
+            Self::maybe_shrink(&mut observers);
+        }
+    }
+
+    /// Wakes every registered selector and observer, marking them as disconnected rather than
+    /// merely aborted.
+    ///
+    /// `abort_all` is for the ordinary "lost the race / timed out, go retry" case; `disconnect`
+    /// is for "the other end hung up". A woken thread can tell the two apart through the reason
+    /// code threaded onto its `Context` by `try_disconnect` (mirroring `try_abort`), and a woken
+    /// future can check `AsyncSlot::is_disconnected`. Either way, `send`/`recv` should report a
+    /// `SendError`/`RecvError` immediately instead of re-looping on an empty-but-open channel.
+    pub fn disconnect(&self) {
+        if self.selectors_len.load(Ordering::SeqCst) > 0 {
+            // See the matching comment in `abort_all`: subtract only what we actually drain here
+            // instead of resetting the counter to an absolute 0.
+            let mut removed = 0;
+
+            if let Some(case) = self.take_fast() {
+                removed += 1;
+                if Self::try_disconnect(&case) {
+                    Self::wake(&case);
+                }
+            }
+
+            let mut cases = self.selectors.lock();
+            removed += cases.len();
 
-            self.len.store(0, Ordering::SeqCst);
             for case in cases.drain(..) {
-                if case.context.try_abort() {
-                    case.context.unpark();
+                if Self::try_disconnect(&case) {
+                    Self::wake(&case);
                 }
             }
 
+            self.selectors_len.fetch_sub(removed, Ordering::SeqCst);
             Self::maybe_shrink(&mut cases);
         }
+
+        if self.observers_len.load(Ordering::SeqCst) > 0 {
+            let mut observers = self.observers.lock();
+
+            self.observers_len.store(0, Ordering::SeqCst);
+            for case in observers.drain(..) {
+                if Self::try_disconnect(&case) {
+                    Self::wake(&case);
+                }
+            }
+
+            Self::maybe_shrink(&mut observers);
+        }
     }
 
-    /// Returns `true` if there exists a case which isn't owned by the current thread.
+    /// Returns `true` if there exists a selector case which isn't owned by the current thread.
     #[inline]
     pub fn can_notify(&self) -> bool {
-        if self.len.load(Ordering::SeqCst) > 0 {
-            let cases = self.cases.lock();
-            let thread_id = context::current_thread_id();
+        if self.selectors_len.load(Ordering::SeqCst) == 0 {
+            return false;
+        }
+
+        let thread_id = context::current_thread_id();
 
-            for i in 0..cases.len() {
-                if cases[i].context.thread.id() != thread_id {
-                    return true;
+        // As in `wake_one`/`unregister`, claim the fast slot before looking at what it held so we
+        // never read a `Case` that's concurrently being freed elsewhere, then hand it straight
+        // back — this check is read-only and shouldn't otherwise disturb `fast`.
+        if let Some(case) = self.take_fast() {
+            let can_wake = match &case.waiter {
+                Waiter::Thread(context) => context.thread.id() != thread_id,
+                Waiter::Async(_) => true,
+            };
+            self.restore_fast(case);
+
+            if can_wake {
+                return true;
+            }
+        }
+
+        let cases = self.selectors.lock();
+        for i in 0..cases.len() {
+            match &cases[i].waiter {
+                Waiter::Thread(context) => {
+                    if context.thread.id() != thread_id {
+                        return true;
+                    }
                 }
+                Waiter::Async(_) => return true,
             }
         }
         false
     }
 
+    /// Tries to claim `case` for selection, racing any other thread/task doing the same.
+    fn try_select(case: &Case) -> bool {
+        match &case.waiter {
+            Waiter::Thread(context) => context.try_select(case.case_id, case.packet),
+            Waiter::Async(slot) => slot.try_select(),
+        }
+    }
+
+    /// Tries to claim `case` for an abort (timeout/lost-select), racing selection the same way.
+    fn try_abort(case: &Case) -> bool {
+        match &case.waiter {
+            Waiter::Thread(context) => context.try_abort(),
+            Waiter::Async(slot) => slot.try_abort(),
+        }
+    }
+
+    /// Tries to claim `case` for a disconnect, racing selection/abort the same way.
+    ///
+    /// For `Waiter::Async`, `AsyncSlot::try_disconnect` (above) carries the distinction entirely
+    /// within this module. For `Waiter::Thread`, there's no slot of our own to stash a reason
+    /// code in -- the parked thread can only learn it was disconnected (rather than merely
+    /// aborted) through whatever `Context` itself records, so this relies on a matching
+    /// `Context::try_disconnect` landing in `internal::context` alongside this change, the same
+    /// way `try_select`/`try_abort` already do for `Context`. That module isn't part of this
+    /// change's diff.
+    fn try_disconnect(case: &Case) -> bool {
+        match &case.waiter {
+            Waiter::Thread(context) => context.try_disconnect(),
+            Waiter::Async(slot) => slot.try_disconnect(),
+        }
+    }
+
+    /// Wakes up whichever thread or task owns `case`, once it has been claimed above.
+    fn wake(case: &Case) {
+        match &case.waiter {
+            Waiter::Thread(context) => context.unpark(),
+            Waiter::Async(slot) => slot.waker.wake_by_ref(),
+        }
+    }
+
     /// Shrinks the internal deque if it's capacity is much larger than length.
     fn maybe_shrink(cases: &mut VecDeque<Case>) {
         if cases.capacity() > 32 && cases.len() < cases.capacity() / 4 {
@@ -151,7 +596,173 @@ impl Waker {
 
 impl Drop for Waker {
     fn drop(&mut self) {
-        debug_assert!(self.cases.lock().is_empty());
-        debug_assert_eq!(self.len.load(Ordering::SeqCst), 0);
+        debug_assert!(self.fast.load(Ordering::SeqCst).is_null());
+        debug_assert!(self.selectors.lock().is_empty());
+        debug_assert_eq!(self.selectors_len.load(Ordering::SeqCst), 0);
+        debug_assert!(self.observers.lock().is_empty());
+        debug_assert_eq!(self.observers_len.load(Ordering::SeqCst), 0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::mem;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::Arc;
+    use std::task::{RawWaker, RawWakerVTable};
+    use std::thread;
+
+    /// Builds a `std::task::Waker` that just flips `flag` to `true` when woken, so a test thread
+    /// can tell "I was woken" apart from "I'm still registered" without depending on any executor.
+    fn flag_waker(flag: Arc<AtomicBool>) -> task::Waker {
+        unsafe fn clone(data: *const ()) -> RawWaker {
+            let flag = Arc::from_raw(data as *const AtomicBool);
+            let cloned = flag.clone();
+            mem::forget(flag);
+            RawWaker::new(Arc::into_raw(cloned) as *const (), &VTABLE)
+        }
+        unsafe fn wake(data: *const ()) {
+            let flag = Arc::from_raw(data as *const AtomicBool);
+            flag.store(true, Ordering::SeqCst);
+        }
+        unsafe fn wake_by_ref(data: *const ()) {
+            let flag = Arc::from_raw(data as *const AtomicBool);
+            flag.store(true, Ordering::SeqCst);
+            mem::forget(flag);
+        }
+        unsafe fn drop_waker(data: *const ()) {
+            drop(Arc::from_raw(data as *const AtomicBool));
+        }
+
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake_by_ref, drop_waker);
+        let raw = RawWaker::new(Arc::into_raw(flag) as *const (), &VTABLE);
+        unsafe { task::Waker::from_raw(raw) }
+    }
+
+    /// Spawns `PRODUCERS` threads that each keep one case registered at all times, and drives
+    /// `wake_one` from the main thread as the sole consumer. Every producer counts how many times
+    /// it gets served; once the run stops, no producer should have been served dramatically less
+    /// often than the busiest one, which is what the rotating cursor in `wake_one` is for — a
+    /// front-loaded scan would instead let a handful of cases dominate and starve the rest.
+    #[test]
+    fn wake_one_is_fair_under_contention() {
+        const PRODUCERS: usize = 8;
+        const TOTAL_WAKES: usize = 4_000;
+
+        let waker = Arc::new(Waker::new());
+        let done = Arc::new(AtomicBool::new(false));
+        let served: Arc<Vec<AtomicUsize>> =
+            Arc::new((0..PRODUCERS).map(|_| AtomicUsize::new(0)).collect());
+
+        let producers: Vec<_> = (0..PRODUCERS)
+            .map(|i| {
+                let waker = waker.clone();
+                let done = done.clone();
+                let served = served.clone();
+                let case_id = CaseId::bogus(i);
+
+                thread::spawn(move || {
+                    while !done.load(Ordering::SeqCst) {
+                        let flag = Arc::new(AtomicBool::new(false));
+                        waker.register_async(case_id, 0, flag_waker(flag.clone()));
+
+                        while !flag.load(Ordering::SeqCst) {
+                            if done.load(Ordering::SeqCst) {
+                                waker.unregister(case_id);
+                                return;
+                            }
+                            thread::yield_now();
+                        }
+
+                        served[i].fetch_add(1, Ordering::SeqCst);
+                    }
+                })
+            })
+            .collect();
+
+        for _ in 0..TOTAL_WAKES {
+            loop {
+                if waker.wake_one().is_some() {
+                    break;
+                }
+                thread::yield_now();
+            }
+        }
+
+        done.store(true, Ordering::SeqCst);
+        for producer in producers {
+            producer.join().unwrap();
+        }
+
+        let counts: Vec<usize> = served.iter().map(|c| c.load(Ordering::SeqCst)).collect();
+        let min = *counts.iter().min().unwrap();
+        let max = *counts.iter().max().unwrap();
+
+        assert!(
+            max - min < TOTAL_WAKES / PRODUCERS,
+            "uneven service across producers: {:?}",
+            counts,
+        );
+    }
+
+    /// Sanity check for the single-waiter lock-free path: a lone registration should be handed
+    /// straight back out of `fast` by `wake_one`, without ever touching `selectors`.
+    #[test]
+    fn fast_path_register_then_wake() {
+        let waker = Waker::new();
+        let case_id = CaseId::bogus(0);
+        let flag = Arc::new(AtomicBool::new(false));
+
+        waker.register_async(case_id, 0, flag_waker(flag.clone()));
+        assert!(waker.selectors.lock().is_empty());
+
+        let case = waker.wake_one().expect("the lone registration should be woken");
+        assert_eq!(case.case_id, case_id);
+        assert!(flag.load(Ordering::SeqCst));
+    }
+
+    /// Hammers `register_async`/`unregister`/`wake_one` against each other from different threads
+    /// so they're constantly racing over the same `fast` slot. This is exactly the scenario the
+    /// claim-before-inspect fix in `take_fast`/`restore_fast` exists for: before that fix, a
+    /// registrar's `unregister` and the waking thread's `wake_one` could both dereference the same
+    /// freed `Case` (a use-after-free that a plain `cargo test` run won't reliably catch on its
+    /// own, but that this keeps exercising so it's caught under Miri/TSan/a sanitized CI job).
+    #[test]
+    fn fast_path_concurrent_register_unregister_wake_race() {
+        const ITERATIONS: usize = 20_000;
+
+        let waker = Arc::new(Waker::new());
+
+        let registrar = {
+            let waker = waker.clone();
+            thread::spawn(move || {
+                for i in 0..ITERATIONS {
+                    let case_id = CaseId::bogus(i % 4);
+                    let flag = Arc::new(AtomicBool::new(false));
+                    waker.register_async(case_id, 0, flag_waker(flag));
+
+                    // Either we win the race and unregister our own case before it's woken, or
+                    // a concurrent `wake_one` already claimed it and this is a no-op -- both are
+                    // valid outcomes, the point is racing the two against each other.
+                    waker.unregister(case_id);
+                }
+            })
+        };
+
+        let waking = {
+            let waker = waker.clone();
+            thread::spawn(move || {
+                for _ in 0..ITERATIONS {
+                    waker.wake_one();
+                }
+            })
+        };
+
+        registrar.join().unwrap();
+        waking.join().unwrap();
+
+        // Drain anything left registered so `Waker::drop`'s invariants hold.
+        while waker.wake_one().is_some() {}
     }
 }